@@ -12,14 +12,36 @@
 
 use crate::getenv_use_multiple_threads;
 use ndarray::prelude::*;
-use numpy::PyReadonlyArray2;
+use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
 use pyo3::prelude::*;
 use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 
 use crate::nlayout::PhysicalQubit;
 
+/// A score/node pair ordered by score so it can be used as a min-heap entry
+/// in a `BinaryHeap`, which is otherwise a max-heap.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct MinScore(f64, usize);
+
+impl Eq for MinScore {}
+
+impl Ord for MinScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.partial_cmp(&self.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for MinScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// A simple container that contains a vector of vectors representing
-/// neighbors of each node in the coupling map
+/// neighbors of each node in the coupling map, along with the weight of
+/// the edge to each of those neighbors taken from the adjacency matrix.
 ///
 /// This object is typically created once from the adjacency matrix of
 /// a coupling map, for example::
@@ -28,59 +50,333 @@ use crate::nlayout::PhysicalQubit;
 ///
 /// and used solely to represent neighbors of each node in qiskit-terra's rust
 /// module.
+///
+/// For a directed coupling map (``directed=True``), `out_neighbors` and
+/// `in_neighbors` are built separately from the rows and columns of the
+/// adjacency matrix respectively, and `out_weights` carries the weight of
+/// each outgoing edge. `neighbors`/`weights` remain as the union of the two
+/// directions purely for backward compatibility with callers that predate
+/// directed coupling-map support: they do not represent direction-aware
+/// traversal costs, and anything that cares about edge direction (such as a
+/// weighted distance matrix) must use `out_neighbors`/`out_weights` instead.
 #[pyclass(module = "qiskit._accelerate.sabre_swap")]
 #[derive(Clone, Debug)]
 pub struct NeighborTable {
     pub neighbors: Vec<Vec<PhysicalQubit>>,
+    pub weights: Vec<Vec<f64>>,
+    pub out_neighbors: Vec<Vec<PhysicalQubit>>,
+    pub out_weights: Vec<Vec<f64>>,
+    pub in_neighbors: Vec<Vec<PhysicalQubit>>,
+    pub directed: bool,
+}
+
+/// Scan a single row or column of the adjacency matrix into a list of
+/// neighbor qubits and the weight of the edge to each of them.
+fn build_edges(values: ArrayView1<f64>) -> PyResult<(Vec<PhysicalQubit>, Vec<f64>)> {
+    let mut edge_neighbors = Vec::new();
+    let mut edge_weights = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        if *value == 0. {
+            continue;
+        }
+        let qubit = match index.try_into() {
+            Ok(index) => PhysicalQubit::new(index),
+            Err(err) => return Err(err.into()),
+        };
+        edge_neighbors.push(qubit);
+        edge_weights.push(*value);
+    }
+    Ok((edge_neighbors, edge_weights))
 }
 
 #[pymethods]
 impl NeighborTable {
     #[new]
-    #[pyo3(text_signature = "(/, adjacency_matrix=None)")]
-    pub fn new(adjacency_matrix: Option<PyReadonlyArray2<f64>>) -> PyResult<Self> {
+    #[pyo3(signature = (adjacency_matrix=None, directed=false))]
+    #[pyo3(text_signature = "(/, adjacency_matrix=None, directed=False)")]
+    pub fn new(adjacency_matrix: Option<PyReadonlyArray2<f64>>, directed: bool) -> PyResult<Self> {
         let run_in_parallel = getenv_use_multiple_threads();
-        let neighbors = match adjacency_matrix {
+        let (out_neighbors, out_weights) = match &adjacency_matrix {
             Some(adjacency_matrix) => {
                 let adj_mat = adjacency_matrix.as_array();
-                let build_neighbors = |row: ArrayView1<f64>| -> PyResult<Vec<PhysicalQubit>> {
-                    row.iter()
-                        .enumerate()
-                        .filter_map(|(row_index, value)| {
-                            if *value == 0. {
-                                None
-                            } else {
-                                Some(match row_index.try_into() {
-                                    Ok(index) => Ok(PhysicalQubit::new(index)),
-                                    Err(err) => Err(err.into()),
-                                })
-                            }
-                        })
-                        .collect()
-                };
-                if run_in_parallel {
+                let rows: Vec<(Vec<PhysicalQubit>, Vec<f64>)> = if run_in_parallel {
                     adj_mat
                         .axis_iter(Axis(0))
                         .into_par_iter()
-                        .map(|row| build_neighbors(row))
+                        .map(build_edges)
                         .collect::<PyResult<_>>()?
                 } else {
                     adj_mat
                         .axis_iter(Axis(0))
-                        .map(|row| build_neighbors(row))
+                        .map(build_edges)
                         .collect::<PyResult<_>>()?
+                };
+                rows.into_iter().unzip()
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+        let in_neighbors = if !directed {
+            out_neighbors.clone()
+        } else {
+            match &adjacency_matrix {
+                Some(adjacency_matrix) => {
+                    let adj_mat = adjacency_matrix.as_array();
+                    let columns: Vec<Vec<PhysicalQubit>> = if run_in_parallel {
+                        adj_mat
+                            .axis_iter(Axis(1))
+                            .into_par_iter()
+                            .map(|column| build_edges(column).map(|(neighbors, _)| neighbors))
+                            .collect::<PyResult<_>>()?
+                    } else {
+                        adj_mat
+                            .axis_iter(Axis(1))
+                            .map(|column| build_edges(column).map(|(neighbors, _)| neighbors))
+                            .collect::<PyResult<_>>()?
+                    };
+                    columns
+                }
+                None => Vec::new(),
+            }
+        };
+        let (neighbors, weights) = if !directed {
+            (out_neighbors.clone(), out_weights.clone())
+        } else {
+            let adj_mat = adjacency_matrix.as_ref().map(|matrix| matrix.as_array());
+            let num_qubits = out_neighbors.len();
+            let mut union_neighbors = Vec::with_capacity(num_qubits);
+            let mut union_weights = Vec::with_capacity(num_qubits);
+            for qubit in 0..num_qubits {
+                let mut row_neighbors = out_neighbors[qubit].clone();
+                let mut row_weights = out_weights[qubit].clone();
+                for predecessor in &in_neighbors[qubit] {
+                    if row_neighbors.contains(predecessor) {
+                        continue;
+                    }
+                    // This qubit only has an *incoming* edge from `predecessor`
+                    // (`predecessor -> qubit`), not an outgoing one, so there is
+                    // no "qubit -> predecessor" weight to report here. The value
+                    // below is the weight of the one edge that does exist between
+                    // the pair; it is for presence/back-compat purposes only and
+                    // must never be read as a directed traversal cost (use
+                    // `out_neighbors`/`out_weights` for that).
+                    let weight = adj_mat
+                        .as_ref()
+                        .map_or(0., |matrix| matrix[[predecessor.index(), qubit]]);
+                    row_neighbors.push(*predecessor);
+                    row_weights.push(weight);
+                }
+                union_neighbors.push(row_neighbors);
+                union_weights.push(row_weights);
+            }
+            (union_neighbors, union_weights)
+        };
+        Ok(NeighborTable {
+            neighbors,
+            weights,
+            out_neighbors,
+            out_weights,
+            in_neighbors,
+            directed,
+        })
+    }
+
+    /// The qubits reachable by an outgoing edge from ``qubit``. For an
+    /// undirected table this is identical to ``neighbors``.
+    fn out_neighbors(&self, qubit: PhysicalQubit) -> Vec<PhysicalQubit> {
+        self.out_neighbors[qubit.index()].clone()
+    }
+
+    /// The qubits that have an edge directed into ``qubit``. For an
+    /// undirected table this is identical to ``neighbors``.
+    fn in_neighbors(&self, qubit: PhysicalQubit) -> Vec<PhysicalQubit> {
+        self.in_neighbors[qubit.index()].clone()
+    }
+
+    /// Compute the all-pairs shortest-path distance matrix by a weighted
+    /// Dijkstra search per source node (run in parallel when
+    /// `getenv_use_multiple_threads()` allows it) over the **outgoing**
+    /// edges of each qubit, i.e. `out_neighbors`/`out_weights`. This means a
+    /// directed coupling map's one-way edges are respected: the distance
+    /// from ``a`` to ``b`` need not equal the distance from ``b`` to ``a``.
+    /// For an undirected table (``directed=False``) `out_neighbors` and
+    /// `out_weights` are identical to `neighbors` and `weights`.
+    ///
+    /// Pairs of qubits that are not connected by any path are given a
+    /// distance of ``inf``.
+    #[pyo3(text_signature = "(/)")]
+    fn distance_matrix<'py>(&self, py: Python<'py>) -> &'py PyArray2<f64> {
+        let num_qubits = self.out_neighbors.len();
+        let run_in_parallel = getenv_use_multiple_threads();
+        let compute_row = |source: usize| -> Vec<f64> {
+            let mut dist = vec![f64::INFINITY; num_qubits];
+            dist[source] = 0.;
+            let mut to_visit = BinaryHeap::new();
+            to_visit.push(MinScore(0., source));
+            while let Some(MinScore(cost, node)) = to_visit.pop() {
+                if cost > dist[node] {
+                    continue;
+                }
+                for (neighbor, weight) in
+                    self.out_neighbors[node].iter().zip(&self.out_weights[node])
+                {
+                    let next_cost = cost + weight;
+                    let neighbor_index = neighbor.index();
+                    if next_cost < dist[neighbor_index] {
+                        dist[neighbor_index] = next_cost;
+                        to_visit.push(MinScore(next_cost, neighbor_index));
+                    }
                 }
             }
-            None => Vec::new(),
+            dist
+        };
+        let rows: Vec<Vec<f64>> = if run_in_parallel {
+            (0..num_qubits).into_par_iter().map(compute_row).collect()
+        } else {
+            (0..num_qubits).map(compute_row).collect()
         };
-        Ok(NeighborTable { neighbors })
+        let mut matrix = Array2::from_elem((num_qubits, num_qubits), f64::INFINITY);
+        for (row_index, row) in rows.into_iter().enumerate() {
+            matrix.row_mut(row_index).assign(&Array1::from(row));
+        }
+        matrix.into_pyarray(py)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn __getstate__(
+        &self,
+    ) -> (
+        Vec<Vec<PhysicalQubit>>,
+        Vec<Vec<f64>>,
+        Vec<Vec<PhysicalQubit>>,
+        Vec<Vec<f64>>,
+        Vec<Vec<PhysicalQubit>>,
+        bool,
+    ) {
+        (
+            self.neighbors.clone(),
+            self.weights.clone(),
+            self.out_neighbors.clone(),
+            self.out_weights.clone(),
+            self.in_neighbors.clone(),
+            self.directed,
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn __setstate__(
+        &mut self,
+        state: (
+            Vec<Vec<PhysicalQubit>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<PhysicalQubit>>,
+            Vec<Vec<f64>>,
+            Vec<Vec<PhysicalQubit>>,
+            bool,
+        ),
+    ) {
+        self.neighbors = state.0;
+        self.weights = state.1;
+        self.out_neighbors = state.2;
+        self.out_weights = state.3;
+        self.in_neighbors = state.4;
+        self.directed = state.5;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numpy::PyArray2;
+    use std::sync::Mutex;
+
+    // `getenv_use_multiple_threads` is gated on process-wide environment
+    // variables, so tests that force one branch or the other must not run
+    // concurrently with each other.
+    static ENV_MUTEX: Mutex<()> = Mutex::new(());
+
+    fn table_from(matrix: &[Vec<f64>], directed: bool) -> NeighborTable {
+        Python::with_gil(|py| {
+            let array = PyArray2::from_vec2(py, matrix).unwrap();
+            NeighborTable::new(Some(array.readonly()), directed).unwrap()
+        })
     }
 
-    fn __getstate__(&self) -> Vec<Vec<PhysicalQubit>> {
-        self.neighbors.clone()
+    #[test]
+    fn distance_matrix_uses_edge_weights_and_inf_for_disconnected() {
+        // 0 --1.5-- 1 --2.5-- 2     3 (isolated)
+        let matrix = vec![
+            vec![0., 1.5, 0., 0.],
+            vec![1.5, 0., 2.5, 0.],
+            vec![0., 2.5, 0., 0.],
+            vec![0., 0., 0., 0.],
+        ];
+        let table = table_from(&matrix, false);
+        let distances =
+            Python::with_gil(|py| table.distance_matrix(py).to_owned_array());
+
+        assert_eq!(distances[[0, 0]], 0.);
+        assert_eq!(distances[[0, 1]], 1.5);
+        assert_eq!(distances[[0, 2]], 4.);
+        assert!(distances[[0, 3]].is_infinite());
+        assert!(distances[[3, 0]].is_infinite());
+    }
+
+    #[test]
+    fn directed_table_exposes_separate_in_and_out_neighbors() {
+        // 0 -> 1 (only one way), 1 <-> 2 (both ways)
+        let matrix = vec![
+            vec![0., 1., 0.],
+            vec![0., 0., 1.],
+            vec![0., 1., 0.],
+        ];
+        let table = table_from(&matrix, true);
+
+        assert_eq!(table.out_neighbors(PhysicalQubit::new(0)), vec![PhysicalQubit::new(1)]);
+        assert_eq!(table.in_neighbors(PhysicalQubit::new(0)), Vec::<PhysicalQubit>::new());
+        assert_eq!(table.out_neighbors(PhysicalQubit::new(1)), vec![PhysicalQubit::new(2)]);
+        assert_eq!(table.in_neighbors(PhysicalQubit::new(1)), vec![PhysicalQubit::new(0), PhysicalQubit::new(2)]);
+
+        // `neighbors` is the backward-compatible union and must not duplicate
+        // a qubit that is reachable in both directions.
+        assert_eq!(table.neighbors[1].len(), 2);
+        assert!(table.neighbors[1].contains(&PhysicalQubit::new(0)));
+        assert!(table.neighbors[1].contains(&PhysicalQubit::new(2)));
     }
 
-    fn __setstate__(&mut self, state: Vec<Vec<PhysicalQubit>>) {
-        self.neighbors = state
+    #[test]
+    fn distance_matrix_respects_direction_on_a_one_way_edge() {
+        // 0 -> 1 only; there is no edge back from 1 to 0.
+        let matrix = vec![vec![0., 1.], vec![0., 0.]];
+        let table = table_from(&matrix, true);
+        let distances =
+            Python::with_gil(|py| table.distance_matrix(py).to_owned_array());
+
+        assert_eq!(distances[[0, 1]], 1.);
+        assert!(distances[[1, 0]].is_infinite());
+    }
+
+    #[test]
+    fn distance_matrix_parallel_and_serial_agree() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        let matrix = vec![
+            vec![0., 1.5, 0., 0.],
+            vec![1.5, 0., 2.5, 0.],
+            vec![0., 2.5, 0., 4.],
+            vec![0., 0., 4., 0.],
+        ];
+        let table = table_from(&matrix, false);
+
+        std::env::set_var("QISKIT_IN_PARALLEL", "TRUE");
+        std::env::set_var("QISKIT_FORCE_THREADS", "FALSE");
+        let serial =
+            Python::with_gil(|py| table.distance_matrix(py).to_owned_array());
+
+        std::env::set_var("QISKIT_FORCE_THREADS", "TRUE");
+        let parallel =
+            Python::with_gil(|py| table.distance_matrix(py).to_owned_array());
+        std::env::remove_var("QISKIT_IN_PARALLEL");
+        std::env::remove_var("QISKIT_FORCE_THREADS");
+
+        assert_eq!(serial, parallel);
     }
 }